@@ -0,0 +1,288 @@
+//! Folds one or more [`crate::prover::Snark`]s into a single outer proof.
+//!
+//! Each inner `Snark` is succinctly verified with `snark-verifier`'s native `PlonkVerifier`,
+//! producing a KZG accumulator per snark; those accumulators are themselves combined into one
+//! accumulator via `KzgAs`. `AggregationCircuit` re-does this verification *inside* a halo2
+//! circuit (using `snark-verifier`'s halo2 loader instead of the native one) and exposes the
+//! resulting accumulator's `lhs`/`rhs` curve points as its own public instances, so checking a
+//! single pairing against those instances attests to every folded statement at once.
+
+use halo2_proofs::{
+    circuit::{Layouter, SimpleFloorPlanner, Value},
+    halo2curves::bn256::{Bn256, Fr, G1Affine},
+    plonk::{Circuit, ConstraintSystem, Error},
+};
+use snark_verifier::{
+    loader::native::NativeLoader,
+    pcs::kzg::{Bdfg21, Kzg, KzgAccumulator, KzgAs, KzgSuccinctVerifyingKey},
+    system::halo2::{compile, transcript::halo2::PoseidonTranscript, Config},
+    verifier::{plonk::PlonkProtocol, PlonkVerifier, SnarkVerifier},
+};
+
+use crate::prover::{ProvingArtifacts, Snark};
+
+type Plonk = PlonkVerifier<Kzg<Bn256, Bdfg21>>;
+type Svk = KzgSuccinctVerifyingKey<G1Affine>;
+
+/// The `snark-verifier` protocol description of an inner `Snark`'s circuit, paired with the
+/// witness (instances + proof bytes) that the `AggregationCircuit` will re-verify in-circuit.
+#[derive(Clone)]
+struct SnarkWitness {
+    protocol: PlonkProtocol<G1Affine>,
+    instances: Vec<Vec<Value<Fr>>>,
+    proof: Value<Vec<u8>>,
+}
+
+impl SnarkWitness {
+    fn new(protocol: PlonkProtocol<G1Affine>, snark: Snark) -> Self {
+        Self {
+            protocol,
+            instances: snark
+                .instances
+                .into_iter()
+                .map(|col| col.into_iter().map(Value::known).collect())
+                .collect(),
+            proof: Value::known(snark.proof),
+        }
+    }
+}
+
+/// The outer circuit: verifies `snarks` and exposes the folded KZG accumulator as public input.
+#[derive(Clone)]
+pub struct AggregationCircuit {
+    svk: Svk,
+    snarks: Vec<SnarkWitness>,
+    instances: Vec<Fr>,
+    as_proof: Value<Vec<u8>>,
+}
+
+impl AggregationCircuit {
+    /// Succinctly verifies every `Snark` proved under `artifacts`, folds the resulting
+    /// accumulators into one via `KzgAs`, and prepares the witness for the in-circuit re-proof.
+    pub fn new(artifacts: &ProvingArtifacts, snarks: Vec<Snark>) -> Self {
+        assert!(!snarks.is_empty(), "must aggregate at least one snark");
+
+        let svk: Svk = artifacts.params.get_g()[0].into();
+        let protocol = compile(
+            &artifacts.params,
+            &artifacts.vk,
+            Config::kzg().with_num_instance(snarks[0].instances.iter().map(Vec::len).collect()),
+        );
+
+        let accumulators: Vec<_> = snarks
+            .iter()
+            .map(|snark| {
+                let mut transcript = PoseidonTranscript::<NativeLoader, _>::new(snark.proof.as_slice());
+                let proof = Plonk::read_proof(&svk, &protocol, &snark.instances, &mut transcript)
+                    .expect("malformed inner proof");
+                Plonk::succinct_verify(&svk, &protocol, &snark.instances, &proof)
+                    .expect("inner snark failed to verify")
+            })
+            .collect();
+
+        let (accumulator, as_proof) = {
+            let mut transcript = PoseidonTranscript::<NativeLoader, Vec<u8>>::new(Vec::new());
+            let accumulator = KzgAs::<Kzg<Bn256, Bdfg21>>::create_proof(
+                &Default::default(),
+                &accumulators,
+                &mut transcript,
+                rand::rngs::OsRng,
+            )
+            .expect("failed to fold accumulators");
+            (accumulator, transcript.finalize())
+        };
+
+        let KzgAccumulator { lhs, rhs } = accumulator;
+        let instances = [lhs.x, lhs.y, rhs.x, rhs.y]
+            .into_iter()
+            .flat_map(crate::aggregation::fe_to_limbs)
+            .collect();
+
+        let snarks = snarks
+            .into_iter()
+            .map(|snark| SnarkWitness::new(protocol.clone(), snark))
+            .collect();
+
+        Self {
+            svk,
+            snarks,
+            instances,
+            as_proof: Value::known(as_proof),
+        }
+    }
+
+    /// The accumulator limbs this circuit exposes as public instances, i.e. the instance column
+    /// to prove or verify the aggregated proof against.
+    pub fn instances(&self) -> Vec<Fr> {
+        self.instances.clone()
+    }
+}
+
+const LIMBS: usize = 4;
+const BITS: usize = 68;
+
+/// Splits a base-field element of the pairing curve into `LIMBS` limbs of `BITS` bits each so it
+/// can be represented over the circuit's (different) scalar field, matching the non-native
+/// arithmetic `snark-verifier`'s halo2 loader uses for curve points.
+fn fe_to_limbs(fe: halo2_proofs::halo2curves::bn256::Fq) -> Vec<Fr> {
+    snark_verifier::util::arithmetic::fe_to_limbs::<_, Fr, LIMBS, BITS>(fe)
+}
+
+/// Columns the in-circuit accumulator verification is built on: a halo2-ecc `BaseFieldEccChip`
+/// config wide enough for the non-native curve-point arithmetic, plus the instance column the
+/// folded accumulator's limbs are exposed through.
+#[derive(Clone)]
+pub struct AggregationConfig {
+    ecc_chip_config: snark_verifier::loader::halo2::EccChipConfig,
+    instance: halo2_proofs::plonk::Column<halo2_proofs::plonk::Instance>,
+}
+
+impl AggregationConfig {
+    fn configure(meta: &mut ConstraintSystem<Fr>) -> Self {
+        let ecc_chip_config = snark_verifier::loader::halo2::EccChipConfig::configure::<G1Affine>(meta, LIMBS, BITS);
+        let instance = meta.instance_column();
+        meta.enable_equality(instance);
+        Self {
+            ecc_chip_config,
+            instance,
+        }
+    }
+}
+
+impl Circuit<Fr> for AggregationCircuit {
+    type Config = AggregationConfig;
+    type FloorPlanner = SimpleFloorPlanner;
+    type Params = ();
+
+    fn without_witnesses(&self) -> Self {
+        Self {
+            svk: self.svk,
+            snarks: self
+                .snarks
+                .iter()
+                .map(|s| SnarkWitness {
+                    protocol: s.protocol.clone(),
+                    instances: s
+                        .instances
+                        .iter()
+                        .map(|col| vec![Value::unknown(); col.len()])
+                        .collect(),
+                    proof: Value::unknown(),
+                })
+                .collect(),
+            instances: self.instances.clone(),
+            as_proof: Value::unknown(),
+        }
+    }
+
+    fn configure(meta: &mut ConstraintSystem<Fr>) -> Self::Config {
+        Self::Config::configure(meta)
+    }
+
+    fn synthesize(&self, config: Self::Config, mut layouter: impl Layouter<Fr>) -> Result<(), Error> {
+        self.verify_and_expose_accumulator(config, &mut layouter)
+    }
+}
+
+impl AggregationCircuit {
+    // Re-runs the succinct verification of every snark inside the circuit using
+    // `snark-verifier`'s halo2 loader (rather than the native loader `new` used), folds the
+    // resulting accumulators with the same `KzgAs` scheme, constrains the witnessed `as_proof`
+    // to match, and constrains the folded `lhs`/`rhs` limbs to `config.instance`.
+    fn verify_and_expose_accumulator(
+        &self,
+        config: AggregationConfig,
+        layouter: &mut impl Layouter<Fr>,
+    ) -> Result<(), Error> {
+        use snark_verifier::loader::halo2::{EccChip, Halo2Loader};
+
+        let ecc_chip = EccChip::<G1Affine>::construct(config.ecc_chip_config);
+
+        layouter.assign_region(
+            || "aggregate",
+            |region| {
+                let loader = Halo2Loader::new(ecc_chip.clone(), region);
+
+                let accumulators: Vec<_> = self
+                    .snarks
+                    .iter()
+                    .map(|snark| {
+                        let mut transcript =
+                            snark_verifier::loader::halo2::PoseidonTranscript::new(&loader, snark.proof.as_ref());
+                        let instances = snark_verifier::loader::halo2::assign_instances(&loader, &snark.instances);
+                        let proof = Plonk::read_proof(&self.svk, &snark.protocol, &instances, &mut transcript)
+                            .expect("malformed inner proof");
+                        Plonk::succinct_verify(&self.svk, &snark.protocol, &instances, &proof)
+                            .expect("inner snark failed to verify")
+                    })
+                    .collect();
+
+                let accumulator = {
+                    let mut transcript =
+                        snark_verifier::loader::halo2::PoseidonTranscript::new(&loader, self.as_proof.as_ref());
+                    KzgAs::<Kzg<Bn256, Bdfg21>>::verify(&Default::default(), &accumulators, &mut transcript)
+                        .expect("folding proof does not match witnessed accumulators")
+                };
+
+                loader.expose_accumulator(layouter.namespace(|| "expose accumulator"), config.instance, accumulator)
+            },
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{base_circuit::FibonacciCircuit, prover};
+    use halo2_proofs::dev::MockProver;
+
+    // Aggregation circuits carry non-native curve-point arithmetic for every folded snark, so
+    // they need a much larger `k` than the statements they aggregate even when folding just one.
+    const AGGREGATION_K: u32 = 21;
+
+    #[test]
+    fn test_aggregation_smoke() {
+        let fib_circuit = FibonacciCircuit::<Fr>::new(9, 6);
+        let artifacts = prover::setup(fib_circuit.k(), &fib_circuit);
+
+        let a = Fr::from(1);
+        let b = Fr::from(1);
+        let out = Fr::from(55);
+        let snark = prover::prove(&artifacts, fib_circuit, vec![vec![a, b, out]]);
+
+        let agg_circuit = AggregationCircuit::new(&artifacts, vec![snark]);
+        let instances = agg_circuit.instances();
+
+        let prover = MockProver::run(AGGREGATION_K, &agg_circuit, vec![instances]).unwrap();
+        assert_eq!(prover.verify(), Ok(()));
+    }
+
+    // Folding two snarks takes `KzgAs::create_proof`/`verify` down the multi-point
+    // random-linear-combination path instead of the length-1 shortcut `test_aggregation_smoke`
+    // exercises, which is the actual "compose multiple statements" capability this module exists
+    // for.
+    #[test]
+    fn test_aggregation_folds_multiple_snarks() {
+        let circuit_a = FibonacciCircuit::<Fr>::new(9, 6);
+        let artifacts = prover::setup(circuit_a.k(), &circuit_a);
+
+        let snark_a = prover::prove(
+            &artifacts,
+            circuit_a,
+            vec![vec![Fr::from(1), Fr::from(1), Fr::from(55)]],
+        );
+
+        let circuit_b = FibonacciCircuit::<Fr>::new(9, 6);
+        let snark_b = prover::prove(
+            &artifacts,
+            circuit_b,
+            vec![vec![Fr::from(2), Fr::from(3), Fr::from(144)]],
+        );
+
+        let agg_circuit = AggregationCircuit::new(&artifacts, vec![snark_a, snark_b]);
+        let instances = agg_circuit.instances();
+
+        let prover = MockProver::run(AGGREGATION_K, &agg_circuit, vec![instances]).unwrap();
+        assert_eq!(prover.verify(), Ok(()));
+    }
+}
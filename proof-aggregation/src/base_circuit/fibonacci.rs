@@ -1,11 +1,10 @@
 //! This circuit is to check whether the output(c) is produced from the specific fibo-input(a,b),
 
 use halo2_proofs::{arithmetic::Field, circuit::*, plonk::*, poly::Rotation};
-use snark_verifier::system::halo2::Config;
 use std::marker::PhantomData;
 
 #[derive(Debug, Clone)]
-struct FibonacciConfig {
+pub(crate) struct FibonacciConfig {
     pub col_a: Column<Advice>,
     pub col_b: Column<Advice>,
     pub col_c: Column<Advice>,
@@ -48,19 +47,77 @@ impl FibonacciConfig {
     }
 }
 
-// This circuit is to check whether the output(c) is produced from the specific fibo-input(a,b),
-// The private input: none.
-// The public input: a,b,c(output)
-#[derive(Default)]
-struct FibonacciCircuit<F>(PhantomData<F>);
+// A thin wrapper around `AssignedCell` so chip consumers depend on a stable type that we can
+// later attach metadata to (e.g. a range tag) without breaking `FiboInstructions`.
+#[derive(Debug, Clone)]
+struct ACell<F: Field>(AssignedCell<F, F>);
 
-impl<F: Field> FibonacciCircuit<F> {
-    #[allow(clippy::type_complexity)]
-    pub fn assign_first_row(
+// The instructions a Fibonacci-step chip must provide, independent of how its columns are laid
+// out. `FibonacciCircuit::synthesize` drives `FiboChip` only through these methods, though the
+// circuit itself constructs `FiboChip` and its `Config` is fixed to `FibonacciConfig`.
+trait FiboInstructions<F: Field>: Chip<F> {
+    type Num;
+
+    fn load_first_row(
+        &self,
+        layouter: impl Layouter<F>,
+    ) -> Result<(Self::Num, Self::Num, Self::Num), Error>;
+
+    fn step(
+        &self,
+        layouter: impl Layouter<F>,
+        prev_b: &Self::Num,
+        prev_c: &Self::Num,
+    ) -> Result<Self::Num, Error>;
+
+    fn expose_public(
+        &self,
+        layouter: impl Layouter<F>,
+        num: &Self::Num,
+        row: usize,
+    ) -> Result<(), Error>;
+}
+
+struct FiboChip<F: Field> {
+    config: FibonacciConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: Field> FiboChip<F> {
+    pub fn construct(config: FibonacciConfig) -> Self {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn configure(meta: &mut ConstraintSystem<F>) -> FibonacciConfig {
+        FibonacciConfig::new(meta)
+    }
+}
+
+impl<F: Field> Chip<F> for FiboChip<F> {
+    type Config = FibonacciConfig;
+    type Loaded = ();
+
+    fn config(&self) -> &Self::Config {
+        &self.config
+    }
+
+    fn loaded(&self) -> &Self::Loaded {
+        &()
+    }
+}
+
+impl<F: Field> FiboInstructions<F> for FiboChip<F> {
+    type Num = ACell<F>;
+
+    fn load_first_row(
         &self,
-        config: &FibonacciConfig,
         mut layouter: impl Layouter<F>,
-    ) -> Result<(AssignedCell<F, F>, AssignedCell<F, F>, AssignedCell<F, F>), Error> {
+    ) -> Result<(Self::Num, Self::Num, Self::Num), Error> {
+        let config = self.config();
+
         layouter.assign_region(
             || "first row",
             |mut region| {
@@ -89,61 +146,105 @@ impl<F: Field> FibonacciCircuit<F> {
                     || a_cell.value().copied() + b_cell.value(),
                 )?;
 
-                Ok((a_cell, b_cell, c_cell))
+                Ok((ACell(a_cell), ACell(b_cell), ACell(c_cell)))
             },
         )
     }
 
-    pub fn assign_row(
+    fn step(
         &self,
-        config: &FibonacciConfig,
         mut layouter: impl Layouter<F>,
-        prev_b: &AssignedCell<F, F>,
-        prev_c: &AssignedCell<F, F>,
-    ) -> Result<AssignedCell<F, F>, Error> {
+        prev_b: &Self::Num,
+        prev_c: &Self::Num,
+    ) -> Result<Self::Num, Error> {
+        let config = self.config();
+
         layouter.assign_region(
             || "next row",
             |mut region| {
                 config.selector.enable(&mut region, 0)?;
 
                 // Copy the value from b & c in previous row to a & b in current row
-                prev_b.copy_advice(|| "a", &mut region, config.col_a, 0)?;
-                prev_c.copy_advice(|| "b", &mut region, config.col_b, 0)?;
+                prev_b.0.copy_advice(|| "a", &mut region, config.col_a, 0)?;
+                prev_c.0.copy_advice(|| "b", &mut region, config.col_b, 0)?;
 
                 let c_cell = region.assign_advice(
                     || "c",
                     config.col_c,
                     0,
-                    || prev_b.value().copied() + prev_c.value(),
+                    || prev_b.0.value().copied() + prev_c.0.value(),
                 )?;
 
-                Ok(c_cell)
+                Ok(ACell(c_cell))
             },
         )
     }
 
-    pub fn expose_public(
+    fn expose_public(
         &self,
-        config: &FibonacciConfig,
         mut layouter: impl Layouter<F>,
-        cell: &AssignedCell<F, F>,
+        num: &Self::Num,
         row: usize,
     ) -> Result<(), Error> {
-        layouter.constrain_instance(cell.cell(), config.instance, row)
+        layouter.constrain_instance(num.0.cell(), self.config().instance, row)
+    }
+}
+
+// `n` is the Fibonacci index being proved (the circuit proves `F(n)`, with `F(0) = a` and
+// `F(1) = b` taken from the public input), and `k` is the degree the circuit is synthesized
+// under, as passed to `FibonacciCircuit::new`; callers read it back through `k()` to size
+// `MockProver::run`/key generation. `k` is not derived from `n`, nor checked against the rows
+// `n` needs.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct FibonacciParams {
+    pub n: usize,
+    pub k: u32,
+}
+
+// This circuit is to check whether the output(c) is produced from the specific fibo-input(a,b),
+// The private input: none.
+// The public input: a,b,c(output)
+pub(crate) struct FibonacciCircuit<F> {
+    params: FibonacciParams,
+    _marker: PhantomData<F>,
+}
+
+impl<F: Field> FibonacciCircuit<F> {
+    pub fn new(n: usize, k: u32) -> Self {
+        assert!(n >= 2, "F(n) needs at least F(0) and F(1) as public input");
+        Self {
+            params: FibonacciParams { n, k },
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn k(&self) -> u32 {
+        self.params.k
     }
 }
 
 impl<F: Field> Circuit<F> for FibonacciCircuit<F> {
     type Config = FibonacciConfig;
     type FloorPlanner = SimpleFloorPlanner;
-    type Params = ();
+    type Params = FibonacciParams;
 
     fn without_witnesses(&self) -> Self {
-        Self::default()
+        Self {
+            params: self.params.clone(),
+            _marker: PhantomData,
+        }
     }
 
-    fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
-        Self::Config::new(meta)
+    fn params(&self) -> Self::Params {
+        self.params.clone()
+    }
+
+    fn configure_with_params(meta: &mut ConstraintSystem<F>, _params: Self::Params) -> Self::Config {
+        FiboChip::configure(meta)
+    }
+
+    fn configure(_meta: &mut ConstraintSystem<F>) -> Self::Config {
+        unreachable!("FibonacciCircuit uses configure_with_params instead")
     }
 
     fn synthesize(
@@ -151,18 +252,141 @@ impl<F: Field> Circuit<F> for FibonacciCircuit<F> {
         config: Self::Config,
         mut layouter: impl Layouter<F>,
     ) -> Result<(), Error> {
+        let chip = FiboChip::construct(config);
+
         let (_, mut prev_b, mut prev_c) =
-            self.assign_first_row(&config, layouter.namespace(|| "first row"))?;
+            chip.load_first_row(layouter.namespace(|| "first row"))?;
 
-        for _i in 3..10 {
-            let c_cell =
-                self.assign_row(&config, layouter.namespace(|| "next row"), &prev_b, &prev_c)?;
+        // `load_first_row` already produced F(2); step through F(3)..=F(n).
+        for _i in 3..=self.params.n {
+            let c_cell = chip.step(layouter.namespace(|| "next row"), &prev_b, &prev_c)?;
             prev_b = prev_c;
             prev_c = c_cell;
         }
 
         // check with the public input.
-        self.expose_public(&config, layouter.namespace(|| "out"), &prev_c, 2)?;
+        chip.expose_public(layouter.namespace(|| "out"), &prev_c, 2)?;
+
+        Ok(())
+    }
+}
+
+// An alternate layout of the same statement that uses a single advice column instead of three.
+// Row `i` holds `F(i)` and the gate reaches across `Rotation::cur()`, `Rotation::next()` and
+// `Rotation(2)` to check `F(i) + F(i+1) = F(i+2)`, so consecutive rows chain by adjacency and no
+// `copy_advice`/equality constraint is needed between them. This uses roughly a third of the
+// advice area of `FibonacciConfig`/`FibonacciCircuit` for the same sequence length, at the cost of
+// a wider gate; the two are kept side by side so they can be benchmarked against each other.
+#[derive(Debug, Clone)]
+pub(crate) struct CompactFibonacciConfig {
+    pub advice: Column<Advice>,
+    pub selector: Selector,
+    pub instance: Column<Instance>,
+}
+
+impl CompactFibonacciConfig {
+    pub fn new<F: Field>(meta: &mut ConstraintSystem<F>) -> Self {
+        let advice = meta.advice_column();
+        let selector = meta.selector();
+        let instance = meta.instance_column();
+
+        meta.enable_equality(advice);
+        meta.enable_equality(instance);
+
+        meta.create_gate("add", |meta| {
+            //
+            // advice
+            // ------
+            //   a      <- Rotation::cur()
+            //   b      <- Rotation::next()
+            //   c      <- Rotation(2)
+            //
+            let s = meta.query_selector(selector);
+            let a = meta.query_advice(advice, Rotation::cur());
+            let b = meta.query_advice(advice, Rotation::next());
+            let c = meta.query_advice(advice, Rotation(2));
+            vec![s * (a + b - c)]
+        });
+
+        Self {
+            advice,
+            selector,
+            instance,
+        }
+    }
+}
+
+#[derive(Default)]
+pub(crate) struct CompactFibonacciCircuit<F> {
+    len: usize,
+    _marker: PhantomData<F>,
+}
+
+impl<F: Field> CompactFibonacciCircuit<F> {
+    pub fn new(len: usize) -> Self {
+        Self {
+            len,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<F: Field> Circuit<F> for CompactFibonacciCircuit<F> {
+    type Config = CompactFibonacciConfig;
+    type FloorPlanner = SimpleFloorPlanner;
+    type Params = ();
+
+    fn without_witnesses(&self) -> Self {
+        Self::default()
+    }
+
+    fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+        Self::Config::new(meta)
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<F>,
+    ) -> Result<(), Error> {
+        let out_cell = layouter.assign_region(
+            || "fibonacci sequence",
+            |mut region| {
+                for row in 0..self.len.saturating_sub(2) {
+                    config.selector.enable(&mut region, row)?;
+                }
+
+                let mut a_cell = region.assign_advice_from_instance(
+                    || "f(0)",
+                    config.instance,
+                    0,
+                    config.advice,
+                    0,
+                )?;
+                let mut b_cell = region.assign_advice_from_instance(
+                    || "f(1)",
+                    config.instance,
+                    1,
+                    config.advice,
+                    1,
+                )?;
+
+                for row in 2..self.len {
+                    let c_cell = region.assign_advice(
+                        || "a + b",
+                        config.advice,
+                        row,
+                        || a_cell.value().copied() + b_cell.value(),
+                    )?;
+                    a_cell = b_cell;
+                    b_cell = c_cell;
+                }
+
+                Ok(b_cell)
+            },
+        )?;
+
+        layouter.constrain_instance(out_cell.cell(), config.instance, 2)?;
 
         Ok(())
     }
@@ -171,20 +395,50 @@ impl<F: Field> Circuit<F> for FibonacciCircuit<F> {
 #[cfg(test)]
 mod tests {
     use halo2_proofs::dev::MockProver;
-    use std::marker::PhantomData;
 
     use super::*;
     use halo2_proofs::halo2curves::pasta::Fp;
 
+    // F(0) = a, F(1) = b, F(i) = F(i-2) + F(i-1).
+    fn fib(a: Fp, b: Fp, n: usize) -> Fp {
+        let (mut prev, mut cur) = (a, b);
+        for _ in 1..n {
+            let next = prev + cur;
+            prev = cur;
+            cur = next;
+        }
+        cur
+    }
+
     #[test]
     fn test_fibonacci() {
+        let a = Fp::from(1); // F[0]
+        let b = Fp::from(1); // F[1]
+
+        for n in [9, 15, 20] {
+            let out = fib(a, b, n);
+            let circuit = FibonacciCircuit::<Fp>::new(n, 6);
+
+            let mut public_input = vec![a, b, out];
+
+            let prover = MockProver::run(circuit.k(), &circuit, vec![public_input.clone()]).unwrap();
+            assert_eq!(prover.verify(), Ok(()));
+
+            public_input[2] += Fp::one();
+            let prover = MockProver::run(circuit.k(), &circuit, vec![public_input]).unwrap();
+            assert!(prover.verify().is_err());
+        }
+    }
+
+    #[test]
+    fn test_compact_fibonacci() {
         let k = 4;
 
         let a = Fp::from(1); // F[0]
         let b = Fp::from(1); // F[1]
         let out = Fp::from(55); // F[9]
 
-        let circuit = FibonacciCircuit(PhantomData);
+        let circuit = CompactFibonacciCircuit::new(10);
 
         let mut public_input = vec![a, b, out];
 
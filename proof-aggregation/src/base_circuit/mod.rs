@@ -0,0 +1,3 @@
+mod fibonacci;
+
+pub(crate) use fibonacci::{CompactFibonacciCircuit, FibonacciCircuit};
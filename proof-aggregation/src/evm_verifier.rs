@@ -0,0 +1,106 @@
+//! Generates a Solidity/Yul verifier contract for [`crate::base_circuit::FibonacciCircuit`] via
+//! `snark-verifier`'s EVM loader, and helpers to shape a proof for that contract's calldata.
+
+use std::rc::Rc;
+
+use halo2_proofs::{
+    halo2curves::bn256::{Bn256, Fq, Fr, G1Affine},
+    plonk::{create_proof, ProvingKey, VerifyingKey},
+    poly::kzg::{commitment::ParamsKZG, multiopen::ProverSHPLONK},
+};
+use rand::rngs::OsRng;
+use snark_verifier::{
+    loader::evm::{encode_calldata, EvmLoader, EvmTranscript},
+    system::halo2::{compile, Config},
+    verifier::{plonk::PlonkVerifier, SnarkVerifier},
+};
+
+use crate::base_circuit::FibonacciCircuit;
+
+type Plonk = PlonkVerifier<snark_verifier::pcs::kzg::Kzg<Bn256, snark_verifier::pcs::kzg::Bdfg21>>;
+
+/// Compiles `vk` into Yul and assembles it into the EVM deployment bytecode of the verifier
+/// contract. `num_instance` is the per-column instance count, i.e. `vec![3]` for
+/// `FibonacciCircuit`'s single `(a, b, out)` instance column.
+pub fn gen_evm_verifier(params: &ParamsKZG<Bn256>, vk: &VerifyingKey<G1Affine>, num_instance: Vec<usize>) -> Vec<u8> {
+    let protocol = compile(params, vk, Config::kzg().with_num_instance(num_instance.clone()));
+    let svk = params.get_g()[0].into();
+
+    let loader = EvmLoader::new::<Fq, Fr>();
+    let protocol = protocol.loaded(&loader);
+    let mut transcript = EvmTranscript::<_, Rc<EvmLoader>, _, _>::new(&loader);
+
+    let instances = transcript.load_instances(num_instance);
+    let proof = Plonk::read_proof(&svk, &protocol, &instances, &mut transcript).expect("malformed proof");
+    Plonk::verify(&svk, &protocol, &instances, &proof).expect("proof does not satisfy protocol");
+
+    loader.deployment_code()
+}
+
+/// Proves `circuit` with a Keccak-based transcript, since that is what the EVM verifier
+/// contract (and `encode_calldata`) expect rather than the Poseidon transcript
+/// [`crate::prover`] uses for native/in-circuit verification.
+pub fn gen_evm_proof(
+    params: &ParamsKZG<Bn256>,
+    pk: &ProvingKey<G1Affine>,
+    circuit: FibonacciCircuit<Fr>,
+    instances: Vec<Vec<Fr>>,
+) -> Vec<u8> {
+    let instance_refs: Vec<&[Fr]> = instances.iter().map(Vec::as_slice).collect();
+
+    let mut transcript = EvmTranscript::<G1Affine, _, _, _>::new(Vec::new());
+    create_proof::<_, ProverSHPLONK<_>, _, _, _, _>(
+        params,
+        pk,
+        &[circuit],
+        &[instance_refs.as_slice()],
+        OsRng,
+        &mut transcript,
+    )
+    .expect("create_proof should not fail");
+
+    transcript.finalize()
+}
+
+/// Lays out a `FibonacciCircuit` proof into the calldata format the generated verifier contract
+/// expects: public inputs `(a, b, out)` followed by the proof bytes.
+pub fn encode_fibonacci_calldata(a: Fr, b: Fr, out: Fr, proof: Vec<u8>) -> Vec<u8> {
+    encode_calldata(&[vec![a, b, out]], &proof)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prover;
+    use halo2_proofs::{halo2curves::bn256::Fr, poly::commitment::ParamsProver};
+    use snark_verifier::loader::evm::{Address, ExecutorBuilder};
+
+    fn deploy_and_run(deployment_code: Vec<u8>, calldata: Vec<u8>) -> bool {
+        let mut evm = ExecutorBuilder::default().with_gas_limit(u64::MAX.into()).build();
+
+        let caller = Address::from_low_u64_be(0xfe);
+        let deployment = evm.deploy(caller, deployment_code.into(), 0.into());
+        let verifier = deployment.address.expect("verifier contract failed to deploy");
+        !evm.call_raw(caller, verifier, calldata.into(), 0.into()).reverted
+    }
+
+    #[test]
+    fn test_fibonacci_evm_verifier() {
+        let circuit = FibonacciCircuit::<Fr>::new(9, 6);
+
+        let artifacts = prover::setup(circuit.k(), &circuit);
+        let deployment_code = gen_evm_verifier(&artifacts.params, &artifacts.vk, vec![3]);
+
+        let a = Fr::from(1);
+        let b = Fr::from(1);
+        let out = Fr::from(55);
+        let instances = vec![vec![a, b, out]];
+
+        let proof = gen_evm_proof(&artifacts.params, &artifacts.pk, circuit, instances);
+        let calldata = encode_fibonacci_calldata(a, b, out, proof.clone());
+        assert!(deploy_and_run(deployment_code.clone(), calldata));
+
+        let tampered_calldata = encode_fibonacci_calldata(a, b, out + Fr::one(), proof);
+        assert!(!deploy_and_run(deployment_code, tampered_calldata));
+    }
+}
@@ -0,0 +1,4 @@
+pub mod aggregation;
+pub mod base_circuit;
+pub mod evm_verifier;
+pub mod prover;
@@ -0,0 +1,104 @@
+//! A small KZG proving/verifying pipeline shared by the circuits in this crate, and by the
+//! aggregation layer in [`crate::aggregation`] which folds the `Snark`s produced here.
+
+use halo2_proofs::{
+    halo2curves::bn256::{Bn256, Fr, G1Affine},
+    plonk::{create_proof, keygen_pk, keygen_vk, verify_proof, Circuit, Error, ProvingKey, VerifyingKey},
+    poly::{
+        commitment::ParamsProver,
+        kzg::{
+            commitment::{KZGCommitmentScheme, ParamsKZG},
+            multiopen::{ProverSHPLONK, VerifierSHPLONK},
+            strategy::SingleStrategy,
+        },
+    },
+};
+use rand::rngs::OsRng;
+use snark_verifier::{loader::native::NativeLoader, system::halo2::transcript::halo2::PoseidonTranscript};
+
+type Scheme = KZGCommitmentScheme<Bn256>;
+
+/// The KZG parameters, proving key and verifying key for a single circuit, kept together so a
+/// `Snark` produced under them can always be re-verified or folded into an [`crate::aggregation`]
+/// circuit without re-running `keygen`.
+pub struct ProvingArtifacts {
+    pub params: ParamsKZG<Bn256>,
+    pub pk: ProvingKey<G1Affine>,
+    pub vk: VerifyingKey<G1Affine>,
+}
+
+/// A proof together with the public instances it attests to.
+#[derive(Clone)]
+pub struct Snark {
+    pub instances: Vec<Vec<Fr>>,
+    pub proof: Vec<u8>,
+}
+
+/// Runs `keygen_vk`/`keygen_pk` for `circuit` under a fresh KZG setup of degree `k`.
+pub fn setup<C: Circuit<Fr>>(k: u32, circuit: &C) -> ProvingArtifacts {
+    let params = ParamsKZG::<Bn256>::setup(k, OsRng);
+    let vk = keygen_vk(&params, circuit).expect("keygen_vk should not fail");
+    let pk = keygen_pk(&params, vk.clone(), circuit).expect("keygen_pk should not fail");
+    ProvingArtifacts { params, pk, vk }
+}
+
+/// Proves `circuit` against `instances`, writing the transcript `snark-verifier`'s in-circuit
+/// verifier expects to read back.
+pub fn prove<C: Circuit<Fr>>(artifacts: &ProvingArtifacts, circuit: C, instances: Vec<Vec<Fr>>) -> Snark {
+    let instance_refs: Vec<&[Fr]> = instances.iter().map(Vec::as_slice).collect();
+
+    let mut transcript = PoseidonTranscript::<NativeLoader, Vec<u8>>::new(Vec::new());
+    create_proof::<Scheme, ProverSHPLONK<_>, _, _, _, _>(
+        &artifacts.params,
+        &artifacts.pk,
+        &[circuit],
+        &[instance_refs.as_slice()],
+        OsRng,
+        &mut transcript,
+    )
+    .expect("create_proof should not fail");
+
+    Snark {
+        instances,
+        proof: transcript.finalize(),
+    }
+}
+
+/// Verifies `snark` against `params`/`vk`.
+pub fn verify(params: &ParamsKZG<Bn256>, vk: &VerifyingKey<G1Affine>, snark: &Snark) -> Result<(), Error> {
+    let instance_refs: Vec<&[Fr]> = snark.instances.iter().map(Vec::as_slice).collect();
+    let mut transcript = PoseidonTranscript::<NativeLoader, _>::new(snark.proof.as_slice());
+
+    verify_proof::<Scheme, VerifierSHPLONK<_>, _, _, _>(
+        params,
+        vk,
+        SingleStrategy::new(params),
+        &[instance_refs.as_slice()],
+        &mut transcript,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::base_circuit::FibonacciCircuit;
+    use halo2_proofs::arithmetic::Field;
+
+    #[test]
+    fn test_prove_verify() {
+        let circuit = FibonacciCircuit::<Fr>::new(9, 6);
+        let artifacts = setup(circuit.k(), &circuit);
+
+        let a = Fr::from(1);
+        let b = Fr::from(1);
+        let out = Fr::from(55);
+        let instances = vec![vec![a, b, out]];
+
+        let snark = prove(&artifacts, circuit, instances);
+        assert!(verify(&artifacts.params, &artifacts.vk, &snark).is_ok());
+
+        let mut tampered = snark;
+        tampered.instances[0][2] += Fr::one();
+        assert!(verify(&artifacts.params, &artifacts.vk, &tampered).is_err());
+    }
+}